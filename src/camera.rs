@@ -0,0 +1,111 @@
+use glam::Vec3;
+use wgpu::util::BufferInitDescriptor;
+
+use crate::{
+    inputs::Inputs,
+    renderer_context::{BufferHandle, RendererContext},
+};
+
+const PITCH_LIMIT: f32 = 89.0_f32.to_radians();
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct CameraUniform {
+    position: [f32; 4],
+    forward: [f32; 4],
+    right: [f32; 4],
+    up: [f32; 4],
+}
+
+/// First-person fly camera: position plus a yaw/pitch pair driven by mouse deltas,
+/// from which an orthonormal forward/right/up basis is derived every frame.
+pub struct Camera {
+    position: Vec3,
+    yaw: f32,
+    pitch: f32,
+    mouse_sensitivity: f32,
+    buffer: BufferHandle,
+}
+
+impl Camera {
+    pub fn new(renderer: &mut RendererContext, position: [f32; 3], mouse_sensitivity: f32) -> Self {
+        let position = Vec3::from(position);
+        let yaw = 0.0;
+        let pitch = 0.0;
+
+        let buffer = renderer.new_buffer(&BufferInitDescriptor {
+            label: Some("camera_buffer"),
+            contents: bytemuck::bytes_of(&Camera::uniform(position, yaw, pitch)),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        Camera {
+            position,
+            yaw,
+            pitch,
+            mouse_sensitivity,
+            buffer,
+        }
+    }
+
+    /// Accumulates yaw/pitch from the mouse deltas collected since the last frame.
+    pub fn update(&mut self, inputs: &Inputs) {
+        let (x_delta, y_delta) = inputs.get_mouse_delta();
+        self.yaw += x_delta * self.mouse_sensitivity;
+        self.pitch = (self.pitch - y_delta * self.mouse_sensitivity).clamp(-PITCH_LIMIT, PITCH_LIMIT);
+    }
+
+    pub fn set_position(&mut self, position: Vec3) {
+        self.position = position;
+    }
+
+    pub fn translate(&mut self, delta: Vec3) {
+        self.position += delta;
+    }
+
+    pub fn forward(&self) -> Vec3 {
+        Vec3::new(
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+            self.pitch.cos() * self.yaw.cos(),
+        )
+    }
+
+    pub fn right(&self) -> Vec3 {
+        self.forward().cross(Vec3::Y).normalize()
+    }
+
+    pub fn up(&self) -> Vec3 {
+        self.right().cross(self.forward())
+    }
+
+    pub fn get_buffer(&self) -> BufferHandle {
+        self.buffer
+    }
+
+    pub fn binding_type(&self) -> wgpu::BindingType {
+        wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        }
+    }
+
+    fn uniform(position: Vec3, yaw: f32, pitch: f32) -> CameraUniform {
+        let forward = Vec3::new(pitch.cos() * yaw.sin(), pitch.sin(), pitch.cos() * yaw.cos());
+        let right = forward.cross(Vec3::Y).normalize();
+        let up = right.cross(forward);
+
+        CameraUniform {
+            position: position.extend(0.0).into(),
+            forward: forward.extend(0.0).into(),
+            right: right.extend(0.0).into(),
+            up: up.extend(0.0).into(),
+        }
+    }
+
+    pub fn update_buffer(&self, renderer: &mut RendererContext) {
+        let uniform = Camera::uniform(self.position, self.yaw, self.pitch);
+        renderer.write_buffer(self.buffer, 0, bytemuck::bytes_of(&uniform));
+    }
+}
@@ -0,0 +1,36 @@
+use std::{
+    path::Path,
+    sync::mpsc::{channel, Receiver},
+    time::Duration,
+};
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+/// Watches a directory for filesystem events (used to hot-reload shaders) without
+/// blocking the main loop: events queue up on a channel and are drained in `get_event`.
+pub struct FileWatcher {
+    _watcher: RecommendedWatcher,
+    receiver: Receiver<notify::Event>,
+}
+
+impl FileWatcher {
+    pub fn new<P: AsRef<Path>>(path: P, poll_interval: Duration) -> notify::Result<Self> {
+        let (sender, receiver) = channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let _ = sender.send(event);
+            }
+        })?;
+        watcher.configure(notify::Config::default().with_poll_interval(poll_interval))?;
+        watcher.watch(path.as_ref(), RecursiveMode::Recursive)?;
+
+        Ok(FileWatcher {
+            _watcher: watcher,
+            receiver,
+        })
+    }
+
+    pub fn get_event(&self) -> Option<notify::Event> {
+        self.receiver.try_recv().ok()
+    }
+}
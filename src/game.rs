@@ -2,40 +2,55 @@ use std::{time::Duration, path::Path};
 
 use glam::{Vec3, Vec2, UVec3};
 use thiserror::Error;
+use wgpu::util::BufferInitDescriptor;
 use winit::event::VirtualKeyCode;
 
 use crate::{
-    time_step::TimeStep, 
-    system::System, 
+    time_step::TimeStep,
+    system::System,
     globals::Globals,
-    camera::Camera, 
+    camera::Camera,
     inputs::Inputs,
+    mesh_voxelizer::{voxelize_obj, MeshVoxelizerError, VoxelizeOptions},
     renderer_context::{
-        RendererContext, 
-        ComputePassDesc, 
-        Binding, 
-        BindingResource, 
-        RenderPassDesc, 
-        PipelineDesc, 
-        ComputePipelineHandle, 
-        RenderPipelineHandle, 
-        TextureHandle, 
-        Resolution, 
-        ShaderHandle, 
-        RendererContextError, 
-        Frame, 
+        RendererContext,
+        ComputePassDesc,
+        Binding,
+        BindingResource,
+        RenderPassDesc,
+        PipelineDesc,
+        ComputePipelineHandle,
+        RenderPipelineHandle,
+        TextureHandle,
+        BufferHandle,
+        Resolution,
+        ShaderHandle,
+        RendererContextError,
+        Frame,
         BindGroupHandle
-    }, 
-    file_watcher::FileWatcher, 
-    utils::make_relative_path, 
-    voxel_world::VoxelWorld, 
+    },
+    file_watcher::FileWatcher,
+    utils::make_relative_path,
+    voxel_world::VoxelWorld,
 };
 
+/// Seed/height parameters uploaded to `generate.wgsl`'s `GenerateParams` uniform.
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GenerateParams {
+    seed: f32,
+    base_height: f32,
+    amplitude: f32,
+    _padding: f32,
+}
+
 
 #[derive(Error, Debug)]
 pub enum GameError {
     #[error("Renderer Context error")]
     RendererContextError(#[from] RendererContextError),
+    #[error("Mesh voxelizer error")]
+    MeshVoxelizerError(#[from] MeshVoxelizerError),
 }
 
 
@@ -52,6 +67,11 @@ pub struct Game {
     render_shader: Option<ShaderHandle>,
     render_pipeline: Option<RenderPipelineHandle>,
     render_bind_group: Option<BindGroupHandle>,
+    generate_shader: Option<ShaderHandle>,
+    generate_pipeline: Option<ComputePipelineHandle>,
+    generate_params_buffer: BufferHandle,
+    generate_seed: f32,
+    regenerate_requested: bool,
     file_watcher: FileWatcher,
 }
 
@@ -66,7 +86,7 @@ impl Game {
         let camera = Camera::new(
             renderer,
             [0.0, 0.0, -1.0],
-            1.0,
+            0.003,
         );
 
         let globals = Globals::new(
@@ -74,6 +94,18 @@ impl Game {
             Vec2::new(800.0, 600.0)
         );
 
+        let generate_seed = 0.0;
+        let generate_params_buffer = renderer.new_buffer(&BufferInitDescriptor {
+            label: Some("generate_params_buffer"),
+            contents: bytemuck::bytes_of(&GenerateParams {
+                seed: generate_seed,
+                base_height: 8.0,
+                amplitude: 6.0,
+                _padding: 0.0,
+            }),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
         let output_texture = renderer.new_texture(
             &wgpu::TextureDescriptor {
                 label: None,
@@ -85,7 +117,7 @@ impl Game {
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rgba8Uint,
+                format: wgpu::TextureFormat::Rgba16Float,
                 usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_SRC,
                 view_formats: &[],
             }
@@ -104,6 +136,11 @@ impl Game {
             render_shader: None,
             render_pipeline : None,
             render_bind_group: None,
+            generate_shader: None,
+            generate_pipeline: None,
+            generate_params_buffer,
+            generate_seed,
+            regenerate_requested: false,
             file_watcher,
         }
     }
@@ -131,7 +168,7 @@ impl Game {
                         binding: 0,
                         visibility: wgpu::ShaderStages::FRAGMENT,
                         ty: wgpu::BindingType::Texture {
-                            sample_type: wgpu::TextureSampleType::Uint,
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
                             view_dimension: wgpu::TextureViewDimension::D2,
                             multisampled: false,
                         },
@@ -170,7 +207,7 @@ impl Game {
                         visibility: wgpu::ShaderStages::COMPUTE,
                         ty: wgpu::BindingType::StorageTexture {
                             access: wgpu::StorageTextureAccess::WriteOnly,
-                            format: wgpu::TextureFormat::Rgba8Uint,
+                            format: wgpu::TextureFormat::Rgba16Float,
                             view_dimension: wgpu::TextureViewDimension::D2,
                         },
                         count: None,
@@ -192,6 +229,97 @@ impl Game {
         )
     }
 
+    fn create_generate_pipeline(renderer: &mut RendererContext, shader: ShaderHandle) -> ComputePipelineHandle {
+        renderer.new_compute_pipeline(
+            &PipelineDesc {
+                shader: shader,
+                bindings_layout: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::StorageTexture {
+                            access: wgpu::StorageTextureAccess::WriteOnly,
+                            format: wgpu::TextureFormat::R8Uint,
+                            view_dimension: wgpu::TextureViewDimension::D3,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::COMPUTE,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                ]
+            }
+        )
+    }
+
+    /// Dispatches the terrain generation compute shader once over the world's X/Z
+    /// footprint, replacing the old CPU triple-loop population of the voxel grid.
+    fn regenerate_terrain(&mut self, renderer: &mut RendererContext, seed: f32) {
+        let generate_pipeline = match self.generate_pipeline {
+            Some(pipeline) => pipeline,
+            None => return,
+        };
+
+        self.generate_seed = seed;
+        renderer.write_buffer(
+            self.generate_params_buffer,
+            0,
+            bytemuck::bytes_of(&GenerateParams {
+                seed,
+                base_height: 8.0,
+                amplitude: 6.0,
+                _padding: 0.0,
+            }),
+        );
+
+        let generate_bind_group = renderer.new_compute_bind_group(
+            generate_pipeline,
+            &[
+                Binding {
+                    binding: 0,
+                    resource: BindingResource::Texture(self.world.get_texture()),
+                },
+                Binding {
+                    binding: 1,
+                    resource: BindingResource::Buffer(self.generate_params_buffer),
+                },
+            ],
+        );
+
+        let size = self.world.get_size();
+        let workgroups_x = (size.x + 7) / 8;
+        let workgroups_z = (size.z + 7) / 8;
+        renderer.run_compute_pass(generate_pipeline, generate_bind_group, (workgroups_x, workgroups_z, 1));
+        renderer.destroy_bind_group(generate_bind_group);
+
+        // The compute pass wrote the texture directly; pull it back into the CPU
+        // mirror so later CPU-side reads (e.g. the mesh flood-fill) see real terrain.
+        self.world.sync_from_texture(renderer);
+    }
+
+    /// Loads an `.obj` mesh and stamps it into the voxel world as solid content,
+    /// showing up in the compute raymarch as soon as the next frame's texture upload runs.
+    pub fn load_and_voxelize<P: AsRef<Path>>(&mut self, path: P, target_origin: UVec3, resolution: f32) -> Result<(), GameError> {
+        voxelize_obj(
+            path,
+            &mut self.world,
+            &VoxelizeOptions {
+                target_origin,
+                resolution,
+                fill_interior: true,
+            },
+        )?;
+
+        Ok(())
+    }
+
     pub fn hot_reload(&mut self, renderer: &mut RendererContext) {
         if let Some(watcher_event) = self.file_watcher.get_event() {
             if let notify::EventKind::Modify(_) = watcher_event.kind {
@@ -222,6 +350,18 @@ impl Game {
                                     self.compute_pipeline = Some(Game::create_compute_pipeline(renderer, compute_shader, &self.world, &self.globals, &self.camera))
                                 }
                             }
+                            else if file_stem == "generate" {
+                                if let Some(generate_shader) = self.generate_shader {
+                                    renderer.destroy_shader(generate_shader);
+                                }
+                                if let Some(generate_pipeline) = self.generate_pipeline {
+                                    renderer.destroy_compute_pipeline(generate_pipeline);
+                                }
+                                self.generate_shader = Game::create_shader(renderer, "src/shaders/generate.wgsl");
+                                if let Some(generate_shader) = self.generate_shader {
+                                    self.generate_pipeline = Some(Game::create_generate_pipeline(renderer, generate_shader))
+                                }
+                            }
                         }
                     }
                 }
@@ -234,22 +374,18 @@ impl System for Game {
     fn init(&mut self, renderer: &mut RendererContext) {
         self.render_shader = Game::create_shader(renderer, "src/shaders/render.wgsl");
         self.compute_shader = Game::create_shader(renderer,"src/shaders/compute.wgsl");
+        self.generate_shader = Game::create_shader(renderer, "src/shaders/generate.wgsl");
         if let Some(render_shader) = self.render_shader {
             self.render_pipeline = Some(Game::create_render_pipeline(renderer, render_shader, &self.globals));
         }
         if let Some(compute_shader) = self.compute_shader {
             self.compute_pipeline = Some(Game::create_compute_pipeline(renderer, compute_shader, &self.world, &self.globals, &self.camera));
         }
-
-        for z in 1..self.world.get_size().z-1 {
-            for y in 1..self.world.get_size().y-1 {
-                for x in 1..self.world.get_size().x-1 {
-                    self.world.set_voxel_at(0, &UVec3::new(x, y, z));
-                }
-            }
+        if let Some(generate_shader) = self.generate_shader {
+            self.generate_pipeline = Some(Game::create_generate_pipeline(renderer, generate_shader));
         }
 
-        self.world.set_voxel_at(255, &UVec3::new(8, 8, 8));
+        self.regenerate_terrain(renderer, 0.0);
         self.camera.set_position(Vec3::new(8.0, 8.0, 4.0));
     }
 
@@ -257,17 +393,22 @@ impl System for Game {
         let delta_time = self.time_step.tick();
         let speed = 5.0;
 
+        self.camera.update(&self.inputs);
+
+        let forward = self.camera.forward();
+        let right = self.camera.right();
+
         if self.inputs.get_key_down(VirtualKeyCode::Z) {
-            self.camera.translate(Vec3::Z * delta_time * speed);
+            self.camera.translate(forward * delta_time * speed);
         }
         if self.inputs.get_key_down(VirtualKeyCode::S) {
-            self.camera.translate(Vec3::NEG_Z * delta_time * speed);
+            self.camera.translate(-forward * delta_time * speed);
         }
         if self.inputs.get_key_down(VirtualKeyCode::D) {
-            self.camera.translate(Vec3::X * delta_time * speed);
+            self.camera.translate(right * delta_time * speed);
         }
         if self.inputs.get_key_down(VirtualKeyCode::Q) {
-            self.camera.translate(Vec3::NEG_X * delta_time * speed);
+            self.camera.translate(-right * delta_time * speed);
         }
         if self.inputs.get_key_down(VirtualKeyCode::Space) {
             self.camera.translate(Vec3::Y * delta_time * speed);
@@ -280,9 +421,15 @@ impl System for Game {
     }
 
     fn prepare_rendering(&mut self, renderer: &mut RendererContext) {
+        if self.regenerate_requested {
+            self.regenerate_requested = false;
+            self.regenerate_terrain(renderer, self.generate_seed + 1.0);
+        }
+
         self.camera.update_buffer(renderer);
+        self.globals.update_buffer(renderer);
         self.world.update_texture(renderer);
-        
+
         if let Some(compute_bind_group) = self.compute_bind_group {
             renderer.destroy_bind_group(compute_bind_group);
         }
@@ -350,6 +497,9 @@ impl System for Game {
     }
 
     fn on_key_down(&mut self, key: winit::event::VirtualKeyCode) {
+        if key == VirtualKeyCode::R {
+            self.regenerate_requested = true;
+        }
         self.inputs.on_key_down(key);
     }
 
@@ -370,6 +520,7 @@ impl System for Game {
     }
 
     fn on_mouse_wheel(&mut self, delta: f32) {
+        self.globals.adjust_exposure(delta * 0.1);
         self.inputs.on_mouse_wheel(delta);
     }
 
@@ -387,7 +538,7 @@ impl System for Game {
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rgba8Uint,
+                format: wgpu::TextureFormat::Rgba16Float,
                 usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_SRC,
                 view_formats: &[],
             }
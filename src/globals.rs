@@ -0,0 +1,79 @@
+use glam::Vec2;
+use wgpu::util::BufferInitDescriptor;
+
+use crate::renderer_context::{BufferHandle, RendererContext};
+
+const DEFAULT_EXPOSURE: f32 = 1.0;
+
+/// Per-frame scalars shared by the compute and render shaders (output resolution,
+/// tonemap exposure, etc.), uploaded as a single uniform buffer bound wherever
+/// `Globals` is needed.
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GlobalsUniform {
+    size: Vec2,
+    exposure: f32,
+    _padding: f32,
+}
+
+pub struct Globals {
+    size: Vec2,
+    exposure: f32,
+    buffer: BufferHandle,
+}
+
+impl Globals {
+    pub fn new(renderer: &mut RendererContext, size: Vec2) -> Self {
+        let exposure = DEFAULT_EXPOSURE;
+        let uniform = GlobalsUniform {
+            size,
+            exposure,
+            _padding: 0.0,
+        };
+        let buffer = renderer.new_buffer(&BufferInitDescriptor {
+            label: Some("globals_buffer"),
+            contents: bytemuck::bytes_of(&uniform),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        Globals { size, exposure, buffer }
+    }
+
+    pub fn get_size(&self) -> Vec2 {
+        self.size
+    }
+
+    pub fn set_size(&mut self, size: Vec2) {
+        self.size = size;
+    }
+
+    pub fn get_exposure(&self) -> f32 {
+        self.exposure
+    }
+
+    /// Adjusts exposure by `delta`, clamped to a sane live-tuning range.
+    pub fn adjust_exposure(&mut self, delta: f32) {
+        self.exposure = (self.exposure + delta).clamp(0.1, 10.0);
+    }
+
+    pub fn get_buffer(&self) -> BufferHandle {
+        self.buffer
+    }
+
+    pub fn binding_type(&self) -> wgpu::BindingType {
+        wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        }
+    }
+
+    pub fn update_buffer(&self, renderer: &mut RendererContext) {
+        let uniform = GlobalsUniform {
+            size: self.size,
+            exposure: self.exposure,
+            _padding: 0.0,
+        };
+        renderer.write_buffer(self.buffer, 0, bytemuck::bytes_of(&uniform));
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::HashSet;
+
+use winit::event::{MouseButton, VirtualKeyCode};
+
+/// Raw input state for the current frame. Keys are level-triggered (`get_key_down`),
+/// mouse deltas and wheel are edge-triggered and cleared every `reset`.
+pub struct Inputs {
+    keys_down: HashSet<VirtualKeyCode>,
+    mouse_buttons_down: HashSet<MouseButton>,
+    mouse_delta: (f32, f32),
+    wheel_delta: f32,
+}
+
+impl Inputs {
+    pub fn new() -> Self {
+        Inputs {
+            keys_down: HashSet::new(),
+            mouse_buttons_down: HashSet::new(),
+            mouse_delta: (0.0, 0.0),
+            wheel_delta: 0.0,
+        }
+    }
+
+    pub fn on_key_down(&mut self, key: VirtualKeyCode) {
+        self.keys_down.insert(key);
+    }
+
+    pub fn on_key_up(&mut self, key: VirtualKeyCode) {
+        self.keys_down.remove(&key);
+    }
+
+    pub fn get_key_down(&self, key: VirtualKeyCode) -> bool {
+        self.keys_down.contains(&key)
+    }
+
+    pub fn on_mouse_button_down(&mut self, button: MouseButton) {
+        self.mouse_buttons_down.insert(button);
+    }
+
+    pub fn on_mouse_button_up(&mut self, button: MouseButton) {
+        self.mouse_buttons_down.remove(&button);
+    }
+
+    pub fn get_mouse_button_down(&self, button: MouseButton) -> bool {
+        self.mouse_buttons_down.contains(&button)
+    }
+
+    pub fn on_mouse_move(&mut self, x_delta: f32, y_delta: f32) {
+        self.mouse_delta.0 += x_delta;
+        self.mouse_delta.1 += y_delta;
+    }
+
+    pub fn get_mouse_delta(&self) -> (f32, f32) {
+        self.mouse_delta
+    }
+
+    pub fn on_mouse_wheel(&mut self, delta: f32) {
+        self.wheel_delta += delta;
+    }
+
+    pub fn get_wheel_delta(&self) -> f32 {
+        self.wheel_delta
+    }
+
+    /// Clears the per-frame edge-triggered state. Called at the end of `Game::update`.
+    pub fn reset(&mut self) {
+        self.mouse_delta = (0.0, 0.0);
+        self.wheel_delta = 0.0;
+    }
+}
@@ -0,0 +1,285 @@
+use std::{collections::VecDeque, path::Path};
+
+use glam::{IVec3, UVec3, Vec3};
+use thiserror::Error;
+
+use crate::voxel_world::VoxelWorld;
+
+#[derive(Error, Debug)]
+pub enum MeshVoxelizerError {
+    #[error("failed to load obj file")]
+    Load(#[from] tobj::LoadError),
+}
+
+/// Material tagged onto every voxel stamped by a mesh import, distinct from the
+/// terrain height bands produced by `generate.wgsl`.
+const MESH_MATERIAL: u8 = 4;
+
+pub struct VoxelizeOptions {
+    /// Grid-space origin the mesh's bounding box minimum is placed at.
+    pub target_origin: UVec3,
+    /// Voxels per world unit; controls how finely the mesh is sampled.
+    pub resolution: f32,
+    /// Flood-fills from the grid border afterward and solidifies anything the
+    /// fill can't reach, turning a hollow shell into a filled model.
+    pub fill_interior: bool,
+}
+
+/// Loads an `.obj` file and rasterizes its triangles into `world`, testing each
+/// candidate voxel against the triangle with a separating-axis test so thin
+/// surfaces don't leak between voxels.
+pub fn voxelize_obj<P: AsRef<Path>>(
+    path: P,
+    world: &mut VoxelWorld,
+    options: &VoxelizeOptions,
+) -> Result<(), MeshVoxelizerError> {
+    let (models, _materials) = tobj::load_obj(
+        path.as_ref(),
+        &tobj::LoadOptions {
+            triangulate: true,
+            ..Default::default()
+        },
+    )?;
+
+    let mut mesh_min = Vec3::splat(f32::MAX);
+    for model in &models {
+        for vertex in model.mesh.positions.chunks_exact(3) {
+            mesh_min = mesh_min.min(Vec3::new(vertex[0], vertex[1], vertex[2]));
+        }
+    }
+
+    let to_voxel_space = |v: Vec3| -> Vec3 { (v - mesh_min) * options.resolution + options.target_origin.as_vec3() };
+
+    let mut triangles = Vec::new();
+    for model in &models {
+        for triangle in model.mesh.indices.chunks_exact(3) {
+            let v0 = to_voxel_space(vertex_at(&model.mesh.positions, triangle[0]));
+            let v1 = to_voxel_space(vertex_at(&model.mesh.positions, triangle[1]));
+            let v2 = to_voxel_space(vertex_at(&model.mesh.positions, triangle[2]));
+
+            triangles.push((v0, v1, v2));
+        }
+    }
+
+    rasterize_triangles(world, &triangles);
+
+    if options.fill_interior {
+        fill_interior(world, MESH_MATERIAL);
+    }
+
+    Ok(())
+}
+
+fn vertex_at(positions: &[f32], index: u32) -> Vec3 {
+    let i = index as usize * 3;
+    Vec3::new(positions[i], positions[i + 1], positions[i + 2])
+}
+
+/// Rasterizes every triangle of the mesh in a single parallel pass over the
+/// mesh's whole bounding box, testing each candidate voxel against every
+/// triangle. A per-triangle `set_voxels_parallel` call would mostly split a
+/// handful of voxels across threads — rayon's dispatch overhead dwarfs the
+/// SAT work at that size, so batching the whole mesh into one pass is what
+/// actually benefits from parallelism.
+fn rasterize_triangles(world: &mut VoxelWorld, triangles: &[(Vec3, Vec3, Vec3)]) {
+    if triangles.is_empty() {
+        return;
+    }
+
+    let size = world.get_size().as_vec3();
+    let mut bbox_min = Vec3::splat(f32::MAX);
+    let mut bbox_max = Vec3::splat(f32::MIN);
+    for &(v0, v1, v2) in triangles {
+        bbox_min = bbox_min.min(v0).min(v1).min(v2);
+        bbox_max = bbox_max.max(v0).max(v1).max(v2);
+    }
+
+    let start = bbox_min.floor().max(Vec3::ZERO).as_uvec3();
+    let end = (bbox_max.ceil() + Vec3::ONE).min(size).as_uvec3();
+
+    world.set_voxels_parallel(start, end, |position, current| {
+        let voxel_center = position.as_vec3() + Vec3::splat(0.5);
+        let hit = triangles
+            .iter()
+            .any(|&(v0, v1, v2)| triangle_intersects_voxel(voxel_center, v0, v1, v2));
+        if hit {
+            MESH_MATERIAL
+        } else {
+            current
+        }
+    });
+}
+
+/// Separating-axis test between a triangle and a unit-sized axis-aligned voxel
+/// centered on `box_center` (Akenine-Möller's triangle/box overlap test).
+fn triangle_intersects_voxel(box_center: Vec3, v0: Vec3, v1: Vec3, v2: Vec3) -> bool {
+    const BOX_HALF_SIZE: Vec3 = Vec3::splat(0.5);
+
+    let v0 = v0 - box_center;
+    let v1 = v1 - box_center;
+    let v2 = v2 - box_center;
+
+    let edges = [v1 - v0, v2 - v1, v0 - v2];
+    let box_axes = [Vec3::X, Vec3::Y, Vec3::Z];
+
+    for edge in edges {
+        for axis in box_axes {
+            let test_axis = axis.cross(edge);
+            if test_axis.length_squared() < 1e-8 {
+                continue;
+            }
+            if separated_on_axis(test_axis, v0, v1, v2, BOX_HALF_SIZE) {
+                return false;
+            }
+        }
+    }
+
+    for axis in box_axes {
+        if separated_on_axis(axis, v0, v1, v2, BOX_HALF_SIZE) {
+            return false;
+        }
+    }
+
+    let face_normal = edges[0].cross(edges[1]);
+    if separated_on_axis(face_normal, v0, v1, v2, BOX_HALF_SIZE) {
+        return false;
+    }
+
+    true
+}
+
+fn separated_on_axis(axis: Vec3, v0: Vec3, v1: Vec3, v2: Vec3, box_half_size: Vec3) -> bool {
+    let (p0, p1, p2) = (v0.dot(axis), v1.dot(axis), v2.dot(axis));
+    let radius = box_half_size.x * axis.x.abs() + box_half_size.y * axis.y.abs() + box_half_size.z * axis.z.abs();
+    let (min_p, max_p) = (p0.min(p1).min(p2), p0.max(p1).max(p2));
+
+    min_p > radius || max_p < -radius
+}
+
+/// Flood-fills from the grid border through empty voxels; whatever it can't
+/// reach is enclosed by the shell and gets solidified in-place.
+fn fill_interior(world: &mut VoxelWorld, material: u8) {
+    let (voxels, size) = world.voxels_mut();
+    flood_fill_enclosed_empty_voxels(voxels, size, material);
+    world.mark_dirty_all();
+}
+
+/// Pure flood-fill: walks `voxels` (a flat, row-major `size.x * size.y * size.z`
+/// grid) from every empty border cell, then solidifies any empty cell the fill
+/// never reached with `material`. Takes a plain slice rather than `VoxelWorld` so
+/// the algorithm can be unit-tested without a GPU-backed world.
+fn flood_fill_enclosed_empty_voxels(voxels: &mut [u8], size: UVec3, material: u8) {
+    let index = |p: UVec3| (p.x + p.y * size.x + p.z * size.x * size.y) as usize;
+
+    let mut reached_outside = vec![false; voxels.len()];
+    let mut frontier = VecDeque::new();
+
+    for z in 0..size.z {
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let on_border = x == 0 || y == 0 || z == 0 || x == size.x - 1 || y == size.y - 1 || z == size.z - 1;
+                let position = UVec3::new(x, y, z);
+                if on_border && voxels[index(position)] == 0 {
+                    reached_outside[index(position)] = true;
+                    frontier.push_back(position);
+                }
+            }
+        }
+    }
+
+    const NEIGHBOR_OFFSETS: [IVec3; 6] = [IVec3::X, IVec3::NEG_X, IVec3::Y, IVec3::NEG_Y, IVec3::Z, IVec3::NEG_Z];
+
+    while let Some(position) = frontier.pop_front() {
+        for offset in NEIGHBOR_OFFSETS {
+            let neighbor = position.as_ivec3() + offset;
+            if neighbor.cmplt(IVec3::ZERO).any() || neighbor.x as u32 >= size.x || neighbor.y as u32 >= size.y || neighbor.z as u32 >= size.z {
+                continue;
+            }
+            let neighbor = neighbor.as_uvec3();
+            if reached_outside[index(neighbor)] || voxels[index(neighbor)] != 0 {
+                continue;
+            }
+            reached_outside[index(neighbor)] = true;
+            frontier.push_back(neighbor);
+        }
+    }
+
+    for z in 0..size.z {
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let position = UVec3::new(x, y, z);
+                let i = index(position);
+                if !reached_outside[i] && voxels[i] == 0 {
+                    voxels[i] = material;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn triangle_outside_voxel_does_not_intersect() {
+        let v0 = Vec3::new(5.0, 5.0, 5.0);
+        let v1 = Vec3::new(5.5, 5.0, 5.0);
+        let v2 = Vec3::new(5.0, 5.5, 5.0);
+
+        assert!(!triangle_intersects_voxel(Vec3::new(0.5, 0.5, 0.5), v0, v1, v2));
+    }
+
+    #[test]
+    fn triangle_straddling_voxel_corner_intersects() {
+        let v0 = Vec3::new(-0.5, -0.5, -0.5);
+        let v1 = Vec3::new(1.5, -0.5, -0.5);
+        let v2 = Vec3::new(-0.5, 1.5, -0.5);
+
+        assert!(triangle_intersects_voxel(Vec3::new(0.0, 0.0, 0.0), v0, v1, v2));
+    }
+
+    #[test]
+    fn degenerate_triangle_is_treated_as_a_point_without_panicking() {
+        let point = Vec3::new(0.0, 0.0, 0.0);
+
+        assert!(triangle_intersects_voxel(Vec3::new(0.0, 0.0, 0.0), point, point, point));
+        assert!(!triangle_intersects_voxel(Vec3::new(10.0, 10.0, 10.0), point, point, point));
+    }
+
+    #[test]
+    fn flood_fill_solidifies_interior_of_a_closed_shell() {
+        let size = UVec3::new(5, 5, 5);
+        let mut voxels = vec![0u8; (size.x * size.y * size.z) as usize];
+        let index = |p: UVec3| (p.x + p.y * size.x + p.z * size.x * size.y) as usize;
+
+        // A hollow 5^3 shell one voxel thick, solid material `1`, empty in between.
+        for z in 0..size.z {
+            for y in 0..size.y {
+                for x in 0..size.x {
+                    let on_border = x == 0 || y == 0 || z == 0 || x == size.x - 1 || y == size.y - 1 || z == size.z - 1;
+                    if on_border {
+                        voxels[index(UVec3::new(x, y, z))] = 1;
+                    }
+                }
+            }
+        }
+
+        flood_fill_enclosed_empty_voxels(&mut voxels, size, 4);
+
+        // The enclosed 3^3 interior, e.g. its center (2,2,2), should now be solid with the fill material.
+        assert_eq!(voxels[index(UVec3::new(2, 2, 2))], 4);
+        // The shell itself must be untouched.
+        assert_eq!(voxels[index(UVec3::new(0, 0, 0))], 1);
+    }
+
+    #[test]
+    fn flood_fill_leaves_open_empty_space_untouched() {
+        let size = UVec3::new(5, 5, 5);
+        let mut voxels = vec![0u8; (size.x * size.y * size.z) as usize];
+
+        flood_fill_enclosed_empty_voxels(&mut voxels, size, 4);
+
+        assert!(voxels.iter().all(|&material| material == 0));
+    }
+}
@@ -0,0 +1,359 @@
+use thiserror::Error;
+
+
+#[derive(Error, Debug)]
+pub enum RendererContextError {
+    #[error("shader compilation failed: {0}")]
+    ShaderCompilation(String),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TextureHandle(pub(crate) usize);
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BufferHandle(pub(crate) usize);
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ShaderHandle(pub(crate) usize);
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ComputePipelineHandle(pub(crate) usize);
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RenderPipelineHandle(pub(crate) usize);
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BindGroupHandle(pub(crate) usize);
+
+#[derive(Clone, Copy, Debug)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+pub enum BindingResource {
+    Texture(TextureHandle),
+    Buffer(BufferHandle),
+}
+
+pub struct Binding {
+    pub binding: u32,
+    pub resource: BindingResource,
+}
+
+pub struct PipelineDesc<'a> {
+    pub shader: ShaderHandle,
+    pub bindings_layout: &'a [wgpu::BindGroupLayoutEntry],
+}
+
+pub struct ComputePassDesc {
+    pub pipeline: ComputePipelineHandle,
+    pub bind_group: BindGroupHandle,
+}
+
+pub struct RenderPassDesc {
+    pub pipeline: RenderPipelineHandle,
+    pub bind_group: BindGroupHandle,
+}
+
+/// Thin wrapper around the raw wgpu handles created for a single compute dispatch.
+pub struct ComputePass<'a> {
+    pub(crate) pass: wgpu::ComputePass<'a>,
+}
+
+impl<'a> ComputePass<'a> {
+    pub fn dispatch(&mut self, x: u32, y: u32, z: u32) {
+        self.pass.dispatch_workgroups(x, y, z);
+    }
+}
+
+/// Thin wrapper around the raw wgpu handles created for a single render pass.
+pub struct RenderPass<'a> {
+    pub(crate) pass: wgpu::RenderPass<'a>,
+}
+
+impl<'a> RenderPass<'a> {
+    pub fn draw(&mut self, vertices: std::ops::Range<u32>, instances: std::ops::Range<u32>) {
+        self.pass.draw(vertices, instances);
+    }
+}
+
+pub struct Frame {
+    pub(crate) encoder: wgpu::CommandEncoder,
+    pub(crate) view: wgpu::TextureView,
+}
+
+impl Frame {
+    pub fn begin_compute_pass(&mut self, _desc: &ComputePassDesc) -> ComputePass {
+        let pass = self.encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: None,
+            timestamp_writes: None,
+        });
+        ComputePass { pass }
+    }
+
+    pub fn begin_render_pass(&mut self, _desc: &RenderPassDesc) -> RenderPass {
+        let pass = self.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &self.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        RenderPass { pass }
+    }
+}
+
+/// Owns the wgpu device/queue/surface and arenas of resources referenced
+/// through opaque handles, so the rest of the engine refers to GPU resources by handle
+/// instead of holding wgpu objects itself.
+pub struct RendererContext {
+    pub(crate) device: wgpu::Device,
+    pub(crate) queue: wgpu::Queue,
+    pub(crate) surface: wgpu::Surface,
+    pub(crate) surface_config: wgpu::SurfaceConfiguration,
+    pub(crate) textures: Vec<(wgpu::Texture, wgpu::TextureView)>,
+    pub(crate) buffers: Vec<wgpu::Buffer>,
+    pub(crate) shaders: Vec<wgpu::ShaderModule>,
+    pub(crate) compute_pipelines: Vec<(wgpu::ComputePipeline, wgpu::BindGroupLayout)>,
+    pub(crate) render_pipelines: Vec<(wgpu::RenderPipeline, wgpu::BindGroupLayout)>,
+    pub(crate) bind_groups: Vec<wgpu::BindGroup>,
+}
+
+impl RendererContext {
+    pub fn new_texture(&mut self, desc: &wgpu::TextureDescriptor) -> TextureHandle {
+        let texture = self.device.create_texture(desc);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        self.textures.push((texture, view));
+        TextureHandle(self.textures.len() - 1)
+    }
+
+    pub fn update_texture(&mut self, handle: TextureHandle, desc: &wgpu::TextureDescriptor) {
+        let texture = self.device.create_texture(desc);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        self.textures[handle.0] = (texture, view);
+    }
+
+    pub fn write_texture(
+        &mut self,
+        handle: TextureHandle,
+        data: &[u8],
+        layout: wgpu::ImageDataLayout,
+        origin: wgpu::Origin3d,
+        size: wgpu::Extent3d,
+    ) {
+        let (texture, _) = &self.textures[handle.0];
+        self.queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture,
+                mip_level: 0,
+                origin,
+                aspect: wgpu::TextureAspect::All,
+            },
+            data,
+            layout,
+            size,
+        );
+    }
+
+    pub fn new_buffer(&mut self, desc: &wgpu::util::BufferInitDescriptor) -> BufferHandle {
+        use wgpu::util::DeviceExt;
+        let buffer = self.device.create_buffer_init(desc);
+        self.buffers.push(buffer);
+        BufferHandle(self.buffers.len() - 1)
+    }
+
+    pub fn write_buffer(&mut self, handle: BufferHandle, offset: u64, data: &[u8]) {
+        self.queue.write_buffer(&self.buffers[handle.0], offset, data);
+    }
+
+    pub fn new_shader(&mut self, source: &str) -> Result<ShaderHandle, RendererContextError> {
+        let module = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: None,
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+        self.shaders.push(module);
+        Ok(ShaderHandle(self.shaders.len() - 1))
+    }
+
+    pub fn destroy_shader(&mut self, _handle: ShaderHandle) {}
+    pub fn destroy_render_pipeline(&mut self, _handle: RenderPipelineHandle) {}
+    pub fn destroy_compute_pipeline(&mut self, _handle: ComputePipelineHandle) {}
+    pub fn destroy_bind_group(&mut self, _handle: BindGroupHandle) {}
+
+    pub fn new_compute_pipeline(&mut self, desc: &PipelineDesc) -> ComputePipelineHandle {
+        let shader = &self.shaders[desc.shader.0];
+        let bind_group_layout = self.device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: desc.bindings_layout,
+        });
+        let layout = self.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: None,
+            layout: Some(&layout),
+            module: shader,
+            entry_point: "main",
+        });
+        self.compute_pipelines.push((pipeline, bind_group_layout));
+        ComputePipelineHandle(self.compute_pipelines.len() - 1)
+    }
+
+    pub fn new_render_pipeline(&mut self, desc: &PipelineDesc) -> RenderPipelineHandle {
+        let shader = &self.shaders[desc.shader.0];
+        let bind_group_layout = self.device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: None,
+            entries: desc.bindings_layout,
+        });
+        let layout = self.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = self.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: None,
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: shader,
+                entry_point: "fs_main",
+                targets: &[Some(self.surface_config.format.into())],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+        self.render_pipelines.push((pipeline, bind_group_layout));
+        RenderPipelineHandle(self.render_pipelines.len() - 1)
+    }
+
+    fn resource_view<'a>(&'a self, resource: &BindingResource) -> wgpu::BindingResource<'a> {
+        match resource {
+            BindingResource::Texture(handle) => wgpu::BindingResource::TextureView(&self.textures[handle.0].1),
+            BindingResource::Buffer(handle) => self.buffers[handle.0].as_entire_binding(),
+        }
+    }
+
+    pub fn new_compute_bind_group(&mut self, pipeline: ComputePipelineHandle, bindings: &[Binding]) -> BindGroupHandle {
+        let layout = &self.compute_pipelines[pipeline.0].1;
+        let entries: Vec<_> = bindings
+            .iter()
+            .map(|b| wgpu::BindGroupEntry {
+                binding: b.binding,
+                resource: self.resource_view(&b.resource),
+            })
+            .collect();
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout,
+            entries: &entries,
+        });
+        self.bind_groups.push(bind_group);
+        BindGroupHandle(self.bind_groups.len() - 1)
+    }
+
+    pub fn new_render_bind_group(&mut self, pipeline: RenderPipelineHandle, bindings: &[Binding]) -> BindGroupHandle {
+        let layout = &self.render_pipelines[pipeline.0].1;
+        let entries: Vec<_> = bindings
+            .iter()
+            .map(|b| wgpu::BindGroupEntry {
+                binding: b.binding,
+                resource: self.resource_view(&b.resource),
+            })
+            .collect();
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout,
+            entries: &entries,
+        });
+        self.bind_groups.push(bind_group);
+        BindGroupHandle(self.bind_groups.len() - 1)
+    }
+
+    /// Synchronously reads a texture back to CPU memory (copy to a staging buffer,
+    /// submit, then block until the mapping completes). Used to keep a CPU-side
+    /// mirror in sync after a GPU-only write such as a generation compute pass.
+    ///
+    /// `unpadded_bytes_per_row` is the tightly-packed row size the caller actually
+    /// wants (e.g. `voxels`' row-major layout); wgpu requires buffer copies to use a
+    /// row stride that's a multiple of `COPY_BYTES_PER_ROW_ALIGNMENT`, so the copy
+    /// itself uses a padded stride and this strips the padding back out before
+    /// returning, giving the caller tightly-packed data regardless of texture width.
+    pub fn read_texture(&mut self, handle: TextureHandle, size: wgpu::Extent3d, unpadded_bytes_per_row: u32) -> Vec<u8> {
+        let (texture, _) = &self.textures[handle.0];
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let padded_bytes_per_row = (unpadded_bytes_per_row + align - 1) / align * align;
+        let buffer_size = (padded_bytes_per_row * size.height * size.depth_or_array_layers) as wgpu::BufferAddress;
+        let readback_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("texture_readback"),
+            size: buffer_size,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &readback_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: Some(size.height),
+                },
+            },
+            size,
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        let slice = readback_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        self.device.poll(wgpu::Maintain::Wait);
+
+        let mapped = slice.get_mapped_range();
+        let mut data = Vec::with_capacity((unpadded_bytes_per_row * size.height * size.depth_or_array_layers) as usize);
+        for row in mapped.chunks(padded_bytes_per_row as usize) {
+            data.extend_from_slice(&row[..unpadded_bytes_per_row as usize]);
+        }
+        drop(mapped);
+        readback_buffer.unmap();
+        data
+    }
+
+    /// Runs a single compute dispatch outside of the per-frame `Frame`, submitting
+    /// its own command buffer immediately. Used for one-off jobs like world generation.
+    pub fn run_compute_pass(&mut self, pipeline: ComputePipelineHandle, bind_group: BindGroupHandle, workgroups: (u32, u32, u32)) {
+        let mut encoder = self.device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                label: None,
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.compute_pipelines[pipeline.0].0);
+            pass.set_bind_group(0, &self.bind_groups[bind_group.0], &[]);
+            pass.dispatch_workgroups(workgroups.0, workgroups.1, workgroups.2);
+        }
+        self.queue.submit(Some(encoder.finish()));
+    }
+
+    pub fn resize(&mut self, resolution: Resolution) {
+        self.surface_config.width = resolution.width.max(1);
+        self.surface_config.height = resolution.height.max(1);
+        self.surface.configure(&self.device, &self.surface_config);
+    }
+}
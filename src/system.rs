@@ -0,0 +1,17 @@
+use crate::renderer_context::{Frame, RendererContext};
+
+/// Lifecycle hooks driven by the main loop. `Game` is the only implementor today,
+/// but keeping the loop behind a trait leaves room for other top-level app states.
+pub trait System {
+    fn init(&mut self, renderer: &mut RendererContext);
+    fn update(&mut self);
+    fn prepare_rendering(&mut self, renderer: &mut RendererContext);
+    fn render(&mut self, frame: &mut Frame);
+    fn on_key_down(&mut self, key: winit::event::VirtualKeyCode);
+    fn on_key_up(&mut self, key: winit::event::VirtualKeyCode);
+    fn on_mouse_button_down(&mut self, button: winit::event::MouseButton);
+    fn on_mouse_button_up(&mut self, button: winit::event::MouseButton);
+    fn on_mouse_move(&mut self, x_delta: f32, y_delta: f32);
+    fn on_mouse_wheel(&mut self, delta: f32);
+    fn resize(&mut self, renderer: &mut RendererContext, width: u32, height: u32);
+}
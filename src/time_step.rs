@@ -0,0 +1,21 @@
+use std::time::Instant;
+
+/// Tracks frame-to-frame delta time in seconds.
+pub struct TimeStep {
+    last_tick: Instant,
+}
+
+impl TimeStep {
+    pub fn new() -> Self {
+        TimeStep {
+            last_tick: Instant::now(),
+        }
+    }
+
+    pub fn tick(&mut self) -> f32 {
+        let now = Instant::now();
+        let delta_time = (now - self.last_tick).as_secs_f32();
+        self.last_tick = now;
+        delta_time
+    }
+}
@@ -0,0 +1,8 @@
+use std::path::{Path, PathBuf};
+
+/// Turns an absolute path reported by the file watcher back into one relative
+/// to the current working directory, so it can be compared against `"src/shaders/..."` literals.
+pub fn make_relative_path<P: AsRef<Path>>(path: P) -> std::io::Result<PathBuf> {
+    let cwd = std::env::current_dir()?;
+    Ok(path.as_ref().strip_prefix(&cwd).unwrap_or(path.as_ref()).to_path_buf())
+}
@@ -0,0 +1,276 @@
+use glam::UVec3;
+use rayon::prelude::*;
+
+use crate::renderer_context::{RendererContext, TextureHandle};
+
+const WORLD_SIZE: UVec3 = UVec3::new(32, 32, 32);
+
+/// CPU-side mirror of the voxel grid plus the GPU 3D texture it's uploaded to.
+/// Voxels are stored as a single material id per cell; `0` is reserved for "empty".
+/// Edits only widen `dirty_region`, so `update_texture` re-uploads just the
+/// touched sub-volume instead of the whole grid.
+pub struct VoxelWorld {
+    size: UVec3,
+    voxels: Vec<u8>,
+    texture: TextureHandle,
+    dirty_region: Option<(UVec3, UVec3)>,
+}
+
+impl VoxelWorld {
+    pub fn new(renderer: &mut RendererContext) -> Self {
+        let size = WORLD_SIZE;
+        let voxels = vec![0u8; (size.x * size.y * size.z) as usize];
+
+        let texture = renderer.new_texture(&wgpu::TextureDescriptor {
+            label: Some("voxel_world_texture"),
+            size: wgpu::Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: size.z,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D3,
+            format: wgpu::TextureFormat::R8Uint,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        VoxelWorld {
+            size,
+            voxels,
+            texture,
+            dirty_region: Some((UVec3::ZERO, size)),
+        }
+    }
+
+    pub fn get_size(&self) -> UVec3 {
+        self.size
+    }
+
+    pub fn get_texture(&self) -> TextureHandle {
+        self.texture
+    }
+
+    fn index(&self, position: &UVec3) -> usize {
+        (position.x + position.y * self.size.x + position.z * self.size.x * self.size.y) as usize
+    }
+
+    fn mark_dirty(&mut self, region_min: UVec3, region_max: UVec3) {
+        self.dirty_region = Some(union_region(self.dirty_region, region_min, region_max));
+    }
+
+    pub fn set_voxel_at(&mut self, material: u8, position: &UVec3) {
+        let index = self.index(position);
+        self.voxels[index] = material;
+        self.mark_dirty(*position, *position + UVec3::ONE);
+    }
+
+    pub fn get_voxel_at(&self, position: &UVec3) -> u8 {
+        self.voxels[self.index(position)]
+    }
+
+    /// Direct access to the CPU mirror for callers that need to mutate a large
+    /// span of voxels in place (e.g. the mesh voxelizer's interior flood-fill)
+    /// without going through `set_voxel_at` one cell at a time.
+    pub(crate) fn voxels_mut(&mut self) -> (&mut [u8], UVec3) {
+        (&mut self.voxels, self.size)
+    }
+
+    /// Marks the whole grid dirty, for callers that mutated voxels directly
+    /// through `voxels_mut` instead of `set_voxel_at`/`set_voxels_parallel`.
+    pub(crate) fn mark_dirty_all(&mut self) {
+        let size = self.size;
+        self.mark_dirty(UVec3::ZERO, size);
+    }
+
+    /// Fills `region` (`[region_min, region_max)`) in parallel via rayon: each z-slice
+    /// of the region is computed independently into a staging buffer, then the slices
+    /// are copied into the CPU mirror and only that sub-volume is marked dirty.
+    /// `fill` receives the voxel's current material alongside its position, so callers
+    /// whose region overlaps previously-written voxels can leave them untouched
+    /// (e.g. by returning `current` when they don't want to paint a given cell).
+    pub fn set_voxels_parallel<F>(&mut self, region_min: UVec3, region_max: UVec3, fill: F)
+    where
+        F: Fn(UVec3, u8) -> u8 + Sync,
+    {
+        let Some((region_min, region_max)) = clamp_region(region_min, region_max, self.size) else {
+            return;
+        };
+
+        let width = region_max.x - region_min.x;
+        let height = region_max.y - region_min.y;
+        let size = self.size;
+        let voxels = &self.voxels;
+        let index_of = move |p: UVec3| (p.x + p.y * size.x + p.z * size.x * size.y) as usize;
+
+        let slices: Vec<(u32, Vec<u8>)> = (region_min.z..region_max.z)
+            .into_par_iter()
+            .map(|z| {
+                let mut slice = vec![0u8; (width * height) as usize];
+                for y in 0..height {
+                    for x in 0..width {
+                        let world_position = UVec3::new(region_min.x + x, region_min.y + y, z);
+                        let current = voxels[index_of(world_position)];
+                        slice[(x + y * width) as usize] = fill(world_position, current);
+                    }
+                }
+                (z, slice)
+            })
+            .collect();
+
+        for (z, slice) in slices {
+            for y in 0..height {
+                let row_start = self.index(&UVec3::new(region_min.x, region_min.y + y, z));
+                let row = &slice[(y * width) as usize..((y + 1) * width) as usize];
+                self.voxels[row_start..row_start + width as usize].copy_from_slice(row);
+            }
+        }
+
+        self.mark_dirty(region_min, region_max);
+    }
+
+    /// Reads the GPU texture back into the CPU mirror after something wrote to it
+    /// directly (e.g. the terrain generation compute pass), and clears `dirty_region`
+    /// since the two are now in sync. Without this, later CPU-side reads of the mirror
+    /// (`get_voxel_at`, or the mesh flood-fill through `voxels_mut`) would see stale, all-empty data.
+    pub fn sync_from_texture(&mut self, renderer: &mut RendererContext) {
+        let bytes = renderer.read_texture(
+            self.texture,
+            wgpu::Extent3d {
+                width: self.size.x,
+                height: self.size.y,
+                depth_or_array_layers: self.size.z,
+            },
+            self.size.x,
+        );
+        self.voxels.copy_from_slice(&bytes);
+        self.dirty_region = None;
+    }
+
+    pub fn binding_type() -> wgpu::BindingType {
+        wgpu::BindingType::Texture {
+            sample_type: wgpu::TextureSampleType::Uint,
+            view_dimension: wgpu::TextureViewDimension::D3,
+            multisampled: false,
+        }
+    }
+
+    /// Re-uploads only the voxels touched since the last call, via a single
+    /// partial `write_texture` over the accumulated dirty extent.
+    pub fn update_texture(&mut self, renderer: &mut RendererContext) {
+        let Some((min, max)) = self.dirty_region else {
+            return;
+        };
+
+        let width = max.x - min.x;
+        let height = max.y - min.y;
+        let depth = max.z - min.z;
+
+        let mut staging = Vec::with_capacity((width * height * depth) as usize);
+        for z in min.z..max.z {
+            for y in min.y..max.y {
+                let row_start = self.index(&UVec3::new(min.x, y, z));
+                staging.extend_from_slice(&self.voxels[row_start..row_start + width as usize]);
+            }
+        }
+
+        renderer.write_texture(
+            self.texture,
+            &staging,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(width),
+                rows_per_image: Some(height),
+            },
+            wgpu::Origin3d { x: min.x, y: min.y, z: min.z },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: depth,
+            },
+        );
+
+        self.dirty_region = None;
+    }
+}
+
+/// Clamps `[region_min, region_max)` to `[0, size)`, returning `None` if the
+/// clamped region is empty on any axis. Pulled out of `set_voxels_parallel` so
+/// the clamping logic can be unit-tested without a GPU-backed `VoxelWorld`.
+fn clamp_region(region_min: UVec3, region_max: UVec3, size: UVec3) -> Option<(UVec3, UVec3)> {
+    let region_min = region_min.min(size);
+    let region_max = region_max.min(size);
+    if region_min.cmpge(region_max).any() {
+        None
+    } else {
+        Some((region_min, region_max))
+    }
+}
+
+/// Widens `current` to also cover `[region_min, region_max)`. Pulled out of
+/// `mark_dirty` so the union math can be unit-tested directly.
+fn union_region(current: Option<(UVec3, UVec3)>, region_min: UVec3, region_max: UVec3) -> (UVec3, UVec3) {
+    match current {
+        Some((min, max)) => (min.min(region_min), max.max(region_max)),
+        None => (region_min, region_max),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_region_passes_through_a_region_already_inside_bounds() {
+        let size = UVec3::new(32, 32, 32);
+        let clamped = clamp_region(UVec3::new(4, 4, 4), UVec3::new(8, 8, 8), size);
+
+        assert_eq!(clamped, Some((UVec3::new(4, 4, 4), UVec3::new(8, 8, 8))));
+    }
+
+    #[test]
+    fn clamp_region_caps_a_region_spilling_past_the_world_size() {
+        let size = UVec3::new(32, 32, 32);
+        let clamped = clamp_region(UVec3::new(30, 30, 30), UVec3::new(40, 40, 40), size);
+
+        assert_eq!(clamped, Some((UVec3::new(30, 30, 30), UVec3::new(32, 32, 32))));
+    }
+
+    #[test]
+    fn clamp_region_rejects_a_region_entirely_outside_bounds() {
+        let size = UVec3::new(32, 32, 32);
+
+        assert_eq!(clamp_region(UVec3::new(40, 0, 0), UVec3::new(50, 1, 1), size), None);
+    }
+
+    #[test]
+    fn clamp_region_rejects_a_degenerate_empty_region() {
+        let size = UVec3::new(32, 32, 32);
+
+        assert_eq!(clamp_region(UVec3::new(4, 4, 4), UVec3::new(4, 8, 8), size), None);
+    }
+
+    #[test]
+    fn union_region_with_no_prior_dirty_region_is_just_the_new_region() {
+        let region = union_region(None, UVec3::new(1, 2, 3), UVec3::new(4, 5, 6));
+
+        assert_eq!(region, (UVec3::new(1, 2, 3), UVec3::new(4, 5, 6)));
+    }
+
+    #[test]
+    fn union_region_widens_min_and_max_independently() {
+        let current = Some((UVec3::new(2, 2, 2), UVec3::new(10, 10, 10)));
+        let region = union_region(current, UVec3::new(0, 5, 12), UVec3::new(1, 6, 13));
+
+        assert_eq!(region, (UVec3::new(0, 2, 2), UVec3::new(10, 10, 13)));
+    }
+
+    #[test]
+    fn union_region_is_a_no_op_when_the_new_region_is_already_covered() {
+        let current = Some((UVec3::new(0, 0, 0), UVec3::new(32, 32, 32)));
+        let region = union_region(current, UVec3::new(10, 10, 10), UVec3::new(20, 20, 20));
+
+        assert_eq!(region, (UVec3::new(0, 0, 0), UVec3::new(32, 32, 32)));
+    }
+}